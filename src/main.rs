@@ -1,49 +1,83 @@
-use std::io::stdin;
-use simple_expr_parser::{parse::{parse, ParseError}, structures::EvaluationError};
+use simple_expr_parser::{parse::{parse, ParseError, Position}, structures::{EvaluationError, Value}};
 use unicode_width::UnicodeWidthStr;
+use rustyline::error::ReadlineError;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Completer, Editor, Helper, Highlighter, Hinter, Result as RLResult};
 
-fn main() {
-    let mut input = String::new();
+fn print_caret(input: &str, pos: Position, message: &str) {
+    let line = input.lines().nth(pos.line - 1).unwrap_or("");
+    let before: String = line.chars().take(pos.col - 1).collect();
+    let at = before.width();
+    println!("{}^ {}", " ".repeat(at), message);
+}
+
+// Lets the rustyline editor decide whether an expression spans multiple
+// lines by reusing our own parser as the completeness oracle: an unclosed
+// parenthesis or block comment just means "keep reading".
+#[derive(Completer, Helper, Highlighter, Hinter)]
+struct ExprValidator;
+
+impl Validator for ExprValidator {
+    fn validate(&self, ctx: &mut ValidationContext) -> RLResult<ValidationResult> {
+        Ok(match parse(ctx.input()) {
+            Err(ParseError::UncloseParentheses) | Err(ParseError::UnclosedComment(_)) => ValidationResult::Incomplete,
+            _ => ValidationResult::Valid(None),
+        })
+    }
+}
+
+fn main() -> RLResult<()> {
+    let mut rl = Editor::<ExprValidator, rustyline::history::DefaultHistory>::new()?;
+    rl.set_helper(Some(ExprValidator));
 
     loop {
-        input.clear();
-        stdin().read_line(&mut input).unwrap();
+        let input = match rl.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err),
+        };
+        rl.add_history_entry(input.as_str())?;
 
         let res = parse(&input);
         match res {
             Ok(expr) => {
                 match expr.eval() {
-                    Ok(result) => println!("{}", result),
+                    Ok(Value::Number(result)) => println!("{}", result),
+                    Ok(Value::Bool(result)) => println!("{}", result),
                     Err(err) => {
                         match err {
                             EvaluationError::Overflow => println!("途中計算に算術オーバーフローが発生しました。"),
                             EvaluationError::ZeroDivision => println!("途中計算にゼロ除算が発生しました。"),
+                            EvaluationError::NonIntegerExponent => println!("指数が整数ではありません。"),
+                            EvaluationError::TypeMismatch => println!("真偽値と数値は混在して計算できません。"),
                         }
                     }
                 }
             },
             Err(err) => {
                 match err {
-                    ParseError::ExceptedExpr(_, len) => {
-                        let at = input[..len].width();
-                        println!("{}^ 式が期待されます。", " ".repeat(at));
+                    ParseError::ExceptedExpr(_, pos) => {
+                        print_caret(&input, pos, "式が期待されます。");
                     },
-                    ParseError::ExceptedOp(_, len) => {
-                        let at = input[..len].width();
-                        println!("{}^ 演算子または閉じ括弧が期待されます。", " ".repeat(at));
+                    ParseError::ExceptedOp(_, pos) => {
+                        print_caret(&input, pos, "演算子または閉じ括弧が期待されます。");
                     },
-                    ParseError::InvalidCloseParenthese(len) => {
-                        let at = input[..len].width();
-                        println!("{}^ 対応する開き括弧がありません。", " ".repeat(at));
+                    ParseError::InvalidCloseParenthese(pos) => {
+                        print_caret(&input, pos, "対応する開き括弧がありません。");
                     },
                     ParseError::UncloseParentheses => {
                         println!("括弧が閉じられていません。");
                     },
-                    ParseError::Overflow(num) => {
-                        println!("{}は大きすぎて計算不能です。", num);
+                    ParseError::UnclosedComment(pos) => {
+                        print_caret(&input, pos, "コメントが閉じられていません。");
+                    },
+                    ParseError::Overflow(num, pos) => {
+                        print_caret(&input, pos, &format!("{}は大きすぎて計算不能です。", num));
                     }
                 }
             },
         }
     }
+
+    Ok(())
 }
@@ -1,90 +1,212 @@
 use crate::structures::{Expression, Parentheses, Operator};
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Position {
+    fn start() -> Self {
+        Self { offset: 0, line: 1, col: 1 }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ParseError {
-    ExceptedExpr(Option<char>, usize),
-    ExceptedOp(char, usize),
-    InvalidCloseParenthese(usize),
+    ExceptedExpr(Option<char>, Position),
+    ExceptedOp(char, Position),
+    InvalidCloseParenthese(Position),
     UncloseParentheses,
-    Overflow(String),
+    UnclosedComment(Position),
+    Overflow(String, Position),
 }
 
 type ParseResult<T> = Result<T, ParseError>;
 
 
 pub fn parse(mut input: &str) -> ParseResult<Expression> {
-    let mut offset = 0;
-    skip_whitespace(&mut input, &mut offset);
-    parse_paren(&mut input, &mut offset, true).map(|p| Expression::from(p))
+    let mut pos = Position::start();
+    skip_trivia(&mut input, &mut pos)?;
+    parse_paren(&mut input, &mut pos, true).map(|p| Expression::from(p))
 }
 
-fn next(text: &mut &str, offset: &mut usize) {
+fn advance(text: &mut &str, pos: &mut Position) -> char {
     let c = text.chars().next().unwrap();
     *text = &text[c.len_utf8()..];
-    *offset += c.len_utf8();
-    skip_whitespace(text, offset);
+    pos.offset += c.len_utf8();
+    if c == '\n' {
+        pos.line += 1;
+        pos.col = 1;
+    } else {
+        pos.col += 1;
+    }
+    c
 }
 
-fn skip_whitespace(text: &mut &str, offset: &mut usize) {
+fn next(text: &mut &str, pos: &mut Position) -> ParseResult<()> {
+    advance(text, pos);
+    skip_trivia(text, pos)
+}
+
+// Skips whitespace, `# ...` line comments and nested `#{ ... }#` block
+// comments, looping until none of the three remain.
+fn skip_trivia(text: &mut &str, pos: &mut Position) -> ParseResult<()> {
+    loop {
+        while let Some(c) = text.chars().next() {
+            if !c.is_whitespace() {
+                break;
+            }
+            advance(text, pos);
+        }
+
+        if text.starts_with("#{") {
+            skip_block_comment(text, pos)?;
+        } else if text.starts_with('#') {
+            skip_line_comment(text, pos);
+        } else {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn skip_line_comment(text: &mut &str, pos: &mut Position) {
     while let Some(c) = text.chars().next() {
-        if !c.is_whitespace() {
+        if c == '\n' {
             break;
         }
-        next(text, offset);
+        advance(text, pos);
+    }
+}
+
+fn skip_block_comment(text: &mut &str, pos: &mut Position) -> ParseResult<()> {
+    let start = *pos;
+    advance(text, pos); // '#'
+    advance(text, pos); // '{'
+    let mut depth = 1;
+
+    loop {
+        if text.is_empty() {
+            return Err(ParseError::UnclosedComment(start));
+        } else if text.starts_with("#{") {
+            advance(text, pos);
+            advance(text, pos);
+            depth += 1;
+        } else if text.starts_with("}#") {
+            advance(text, pos);
+            advance(text, pos);
+            depth -= 1;
+            if depth == 0 {
+                return Ok(());
+            }
+        } else {
+            advance(text, pos);
+        }
     }
 }
 
-fn parse_expr(text: &mut &str, offset: &mut usize) -> ParseResult<Expression> {
+fn parse_expr(text: &mut &str, pos: &mut Position) -> ParseResult<Expression> {
+    let mut negate = false;
+    while let Some(c) = text.chars().next() {
+        if c == '+' {
+            next(text, pos)?;
+        } else if c == '-' {
+            next(text, pos)?;
+            negate = !negate;
+        } else {
+            break;
+        }
+    }
+
+    let primary = parse_pow(text, pos)?;
+    Ok(if negate {
+        Expression::Neg(Box::new(primary))
+    } else {
+        primary
+    })
+}
+
+// An atom optionally followed by `^`: this binds tighter than the unary
+// minus wrapped around it in `parse_expr`, so `-2^2` parses as `-(2^2)`
+// rather than `(-2)^2`. The exponent is parsed via `parse_expr` so `^`
+// stays right-associative and its right-hand side keeps its own leading
+// sign (`2^-2` is `2^(-2)`).
+fn parse_pow(text: &mut &str, pos: &mut Position) -> ParseResult<Expression> {
+    let base = parse_atom(text, pos)?;
+    if text.chars().next() == Some('^') {
+        next(text, pos)?;
+        let exponent = parse_expr(text, pos)?;
+        Ok(Expression::from(Parentheses::new(vec![base, exponent], vec![Operator::Pow])))
+    } else {
+        Ok(base)
+    }
+}
+
+fn parse_atom(text: &mut &str, pos: &mut Position) -> ParseResult<Expression> {
     if let Some(c) = text.chars().next() {
         if c.is_ascii_digit() {
+            let start = *pos;
             let mut num = String::new();
             while let Some(c) = text.chars().next() {
                 if c.is_ascii_digit() {
-                    next(text, offset);
+                    // Plain `advance`, not `next`: trivia must not be skipped
+                    // mid-digit-run, or a comment like `1#{x}#2` would get
+                    // silently spliced into the single number `12`.
+                    advance(text, pos);
                     num.push(c);
                 } else {
                     break;
                 }
             }
+            skip_trivia(text, pos)?;
             if let Ok(num) = num.parse::<u64>() {
                 Ok(Expression::from(num))
             } else {
-                Err(ParseError::Overflow(num))
+                Err(ParseError::Overflow(num, start))
             }
         } else if c == '(' {
-            next(text, offset);
-            Ok(Expression::from(parse_paren(text, offset, false)?))
+            next(text, pos)?;
+            Ok(Expression::from(parse_paren(text, pos, false)?))
         } else {
-            Err(ParseError::ExceptedExpr(Some(c), *offset))
+            Err(ParseError::ExceptedExpr(Some(c), *pos))
         }
     } else {
-        return Err(ParseError::ExceptedExpr(None, *offset))
+        return Err(ParseError::ExceptedExpr(None, *pos))
     }
 }
 
-fn parse_paren(text: &mut &str, offset: &mut usize, outermost: bool) -> ParseResult<Parentheses> {
-    let mut exprs = vec![Expression::from(parse_expr(text, offset)?)];
+fn parse_paren(text: &mut &str, pos: &mut Position, outermost: bool) -> ParseResult<Parentheses> {
+    let mut exprs = vec![Expression::from(parse_expr(text, pos)?)];
     let mut ops = vec![];
-    
+
     while let Some(c) = text.chars().next() {
-        if let Some(op) = Operator::from_char(c) {
-            next(text, offset);
+        if let Some(op) = text.chars().nth(1).and_then(|c2| Operator::from_two_chars(c, c2)) {
+            next(text, pos)?;
+            next(text, pos)?;
             ops.push(op);
-            
-            exprs.push(parse_expr(text, offset)?);
-            
+
+            exprs.push(parse_expr(text, pos)?);
+
+        } else if let Some(op) = Operator::from_char(c) {
+            next(text, pos)?;
+            ops.push(op);
+
+            exprs.push(parse_expr(text, pos)?);
+
         } else if c == ')' {
             if !outermost {
-                next(text, offset);
+                next(text, pos)?;
                 return Ok(Parentheses::new(exprs, ops));
             } else {
-                return Err(ParseError::InvalidCloseParenthese(*offset));
+                return Err(ParseError::InvalidCloseParenthese(*pos));
             }
         } else {
-            return Err(ParseError::ExceptedOp(c, *offset));
+            return Err(ParseError::ExceptedOp(c, *pos));
         }
     }
-    
+
     if outermost {
         Ok(Parentheses::new(exprs, ops))
     } else {
@@ -108,8 +230,43 @@ fn test() {
     assert_eq!(parse("1 + (1+2*3) - 2 * ((1+2*3) * 2 ) / 3"), Ok(c));
     assert_eq!(parse("(1)"), Ok(d));
 
-    assert_eq!(parse(""), Err(ParseError::ExceptedExpr(None, 0)));
-    assert_eq!(parse("a"), Err(ParseError::ExceptedExpr(Some('a'), 0)));
+    assert_eq!(parse(""), Err(ParseError::ExceptedExpr(None, Position { offset: 0, line: 1, col: 1 })));
+    assert_eq!(parse("a"), Err(ParseError::ExceptedExpr(Some('a'), Position { offset: 0, line: 1, col: 1 })));
     assert_eq!(parse("(0"), Err(ParseError::UncloseParentheses));
-    assert_eq!(parse("(0+)"), Err(ParseError::ExceptedExpr(Some(')'), 3)));
-}
\ No newline at end of file
+    assert_eq!(parse("(0+)"), Err(ParseError::ExceptedExpr(Some(')'), Position { offset: 3, line: 1, col: 4 })));
+
+    let neg_three = Expression::Neg(Box::new(three.clone()));
+    let e = Expression::from(Parentheses::new(vec![neg_three.clone()], vec![]));
+    assert_eq!(parse("-3"), Ok(e));
+
+    let f = Expression::from(Parentheses::new(vec![two.clone(), neg_three.clone()], vec![Operator::Mul]));
+    assert_eq!(parse("2 * -3"), Ok(f));
+
+    let double_neg = Expression::from(Parentheses::new(vec![Expression::from(Parentheses::new(vec![three.clone(), two.clone()], vec![Operator::Add]))], vec![]));
+    assert_eq!(parse("--(3+2)"), Ok(double_neg));
+
+    // Unary minus binds looser than `^`: `-2^2` is `-(2^2)`, not `(-2)^2`.
+    let neg_pow = Expression::Neg(Box::new(Expression::from(Parentheses::new(vec![two.clone(), two.clone()], vec![Operator::Pow]))));
+    let neg_pow = Expression::from(Parentheses::new(vec![neg_pow], vec![]));
+    assert_eq!(parse("-2^2"), Ok(neg_pow));
+
+    assert_eq!(parse("1\n+ a"), Err(ParseError::ExceptedExpr(Some('a'), Position { offset: 4, line: 2, col: 3 })));
+
+    assert_eq!(parse("1 # a comment\n+ 2"), Ok(b_one_plus_two(&one, &two)));
+    assert_eq!(parse("1 #{ a #{ nested }# block }# + 2"), Ok(b_one_plus_two(&one, &two)));
+    assert_eq!(parse("1 + #{ unterminated"), Err(ParseError::UnclosedComment(Position { offset: 4, line: 1, col: 5 })));
+
+    let g = Expression::from(Parentheses::new(vec![one.clone(), two.clone()], vec![Operator::Le]));
+    assert_eq!(parse("1 <= 2"), Ok(g));
+
+    let h = Expression::from(Parentheses::new(vec![one.clone(), two.clone()], vec![Operator::Ne]));
+    assert_eq!(parse("1!=2"), Ok(h));
+
+    // A comment between two digit runs must not splice them into one number.
+    assert_eq!(parse("1#{x}#2"), Err(ParseError::ExceptedOp('2', Position { offset: 6, line: 1, col: 7 })));
+}
+
+#[cfg(test)]
+fn b_one_plus_two(one: &Expression, two: &Expression) -> Expression {
+    Expression::from(Parentheses::new(vec![one.clone(), two.clone()], vec![Operator::Add]))
+}
@@ -7,30 +7,105 @@ pub enum Operator {
     Sub,
     Mul,
     Div,
+    Pow,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
 }
 
-pub type EvaluationResult = Result<Fraction, EvaluationError>;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Number(Fraction),
+    Bool(bool),
+}
+
+pub type EvaluationResult = Result<Value, EvaluationError>;
 
 impl Operator {
-    fn apply(self, left: Fraction, right: Fraction) -> EvaluationResult {
+    fn apply(self, left: Value, right: Value) -> EvaluationResult {
+        let left = Self::as_number(left)?;
+        let right = Self::as_number(right)?;
+
         match self {
-            Self::Add => left.checked_add(&right).ok_or(EvaluationError::Overflow),
-            Self::Sub => left.checked_sub(&right).ok_or(EvaluationError::Overflow),
-            Self::Mul => left.checked_mul(&right).ok_or(EvaluationError::Overflow),
+            Self::Add => Ok(Value::Number(left.checked_add(&right).ok_or(EvaluationError::Overflow)?)),
+            Self::Sub => Ok(Value::Number(left.checked_sub(&right).ok_or(EvaluationError::Overflow)?)),
+            Self::Mul => Ok(Value::Number(left.checked_mul(&right).ok_or(EvaluationError::Overflow)?)),
             Self::Div => {
                 let raw = left.checked_div(&right).ok_or(EvaluationError::Overflow)?;
                 match raw {
-                    Fraction::Rational(_, _) => Ok(raw),
+                    Fraction::Rational(_, _) => Ok(Value::Number(raw)),
                     _ => Err(EvaluationError::ZeroDivision),
                 }
             },
+            Self::Pow => Ok(Value::Number(Self::apply_pow(left, right)?)),
+            Self::Eq => Ok(Value::Bool(left == right)),
+            Self::Ne => Ok(Value::Bool(left != right)),
+            Self::Lt => Ok(Value::Bool(left < right)),
+            Self::Le => Ok(Value::Bool(left <= right)),
+            Self::Gt => Ok(Value::Bool(left > right)),
+            Self::Ge => Ok(Value::Bool(left >= right)),
+        }
+    }
+
+    fn as_number(value: Value) -> Result<Fraction, EvaluationError> {
+        match value {
+            Value::Number(n) => Ok(n),
+            Value::Bool(_) => Err(EvaluationError::TypeMismatch),
+        }
+    }
+
+    fn apply_pow(base: Fraction, exponent: Fraction) -> Result<Fraction, EvaluationError> {
+        let one = Fraction::from(1u64);
+        let exponent_int = exponent.trunc();
+        if exponent != exponent_int {
+            return Err(EvaluationError::NonIntegerExponent);
+        }
+
+        if exponent >= Fraction::from(0u64) {
+            Self::pow_by_squaring(base, exponent_int)
+        } else {
+            if base == Fraction::from(0u64) {
+                return Err(EvaluationError::ZeroDivision);
+            }
+            let positive = Self::pow_by_squaring(base, -exponent_int)?;
+            one.checked_div(&positive).ok_or(EvaluationError::Overflow)
         }
     }
 
+    // Exponentiation by squaring: O(log count) `checked_mul`s instead of a
+    // linear count-down. A linear loop never overflows (so never
+    // short-circuits) when `base` is `-1`, `0` or `1`, letting an ordinary
+    // u64 exponent like `99999999999999` run for an unbounded time; squaring
+    // keeps the iteration count bounded regardless of base.
+    fn pow_by_squaring(mut base: Fraction, mut count: Fraction) -> Result<Fraction, EvaluationError> {
+        let zero = Fraction::from(0u64);
+        let two = Fraction::from(2u64);
+        let mut result = Fraction::from(1u64);
+
+        while count > zero {
+            let half = count.checked_div(&two).ok_or(EvaluationError::Overflow)?.trunc();
+            let is_odd = count != half.checked_mul(&two).ok_or(EvaluationError::Overflow)?;
+            if is_odd {
+                result = result.checked_mul(&base).ok_or(EvaluationError::Overflow)?;
+            }
+            if half > zero {
+                base = base.checked_mul(&base).ok_or(EvaluationError::Overflow)?;
+            }
+            count = half;
+        }
+
+        Ok(result)
+    }
+
     fn precedence(self) -> usize {
         match self {
-            Self::Mul | Self::Div => 0,
-            Self::Add | Self::Sub => 1,
+            Self::Pow => 0,
+            Self::Mul | Self::Div => 1,
+            Self::Add | Self::Sub => 2,
+            Self::Eq | Self::Ne | Self::Lt | Self::Le | Self::Gt | Self::Ge => 3,
         }
     }
 
@@ -40,6 +115,21 @@ impl Operator {
             '-' => Some(Self::Sub),
             '*' => Some(Self::Mul),
             '/' => Some(Self::Div),
+            '^' => Some(Self::Pow),
+            '<' => Some(Self::Lt),
+            '>' => Some(Self::Gt),
+            _ => None,
+        }
+    }
+
+    // The two-character comparison operators (`==`, `!=`, `<=`, `>=`) need a
+    // lookahead past `from_char`, so the parser tries this first.
+    pub fn from_two_chars(c: char, next: char) -> Option<Self> {
+        match (c, next) {
+            ('=', '=') => Some(Self::Eq),
+            ('!', '=') => Some(Self::Ne),
+            ('<', '=') => Some(Self::Le),
+            ('>', '=') => Some(Self::Ge),
             _ => None,
         }
     }
@@ -49,6 +139,8 @@ impl Operator {
 pub enum EvaluationError {
     ZeroDivision,
     Overflow,
+    NonIntegerExponent,
+    TypeMismatch,
 }
 
 
@@ -56,13 +148,18 @@ pub enum EvaluationError {
 pub enum Expression {
     Num(u64),
     Parentheses(Parentheses),
+    Neg(Box<Expression>),
 }
 
 impl Expression {
     pub fn eval(&self) -> EvaluationResult {
         Ok(match self {
-            Self::Num(value) => Fraction::from(*value),
+            Self::Num(value) => Value::Number(Fraction::from(*value)),
             Self::Parentheses(parenthese) => parenthese.eval()?,
+            Self::Neg(inner) => {
+                let inner = Operator::as_number(inner.eval()?)?;
+                Value::Number(Fraction::from(0u64).checked_sub(&inner).ok_or(EvaluationError::Overflow)?)
+            },
         })
     }
 }
@@ -95,7 +192,7 @@ impl Parentheses {
         }
     }
 
-    fn apply_ops(values: &mut Deque<Fraction>, ops: &mut Deque<Operator>, precedence: usize) -> Result<(), EvaluationError> {
+    fn apply_ops(values: &mut Deque<Value>, ops: &mut Deque<Operator>, precedence: usize) -> Result<(), EvaluationError> {
         let mut res_values = Deque::from(vec![values.pop_front().unwrap()]);
         let mut res_ops = Deque::new();
 
@@ -114,6 +211,27 @@ impl Parentheses {
         Ok(())
     }
 
+    // Right-to-left counterpart of `apply_ops`, used for right-associative
+    // operators such as `Pow` (`2^3^2` must fold as `2^(3^2)`).
+    fn apply_ops_rtl(values: &mut Deque<Value>, ops: &mut Deque<Operator>, precedence: usize) -> Result<(), EvaluationError> {
+        let mut values_vec: Vec<Value> = std::mem::take(values).into_iter().collect();
+        let mut ops_vec: Vec<Operator> = std::mem::take(ops).into_iter().collect();
+
+        let mut i = ops_vec.len();
+        while i > 0 {
+            i -= 1;
+            if ops_vec[i].precedence() == precedence {
+                let right = values_vec.remove(i + 1);
+                let left = values_vec[i];
+                values_vec[i] = ops_vec.remove(i).apply(left, right)?;
+            }
+        }
+
+        *values = values_vec.into_iter().collect();
+        *ops = ops_vec.into_iter().collect();
+        Ok(())
+    }
+
     pub fn eval(&self) -> EvaluationResult {
         let mut values = Deque::new();
         for expr in self.exprs.iter() {
@@ -121,8 +239,12 @@ impl Parentheses {
         }
         let mut ops: Deque<_> = self.operators.iter().copied().collect();
 
-        for prc in 0..2 {
-            Self::apply_ops(&mut values, &mut ops, prc)?;
+        for prc in 0..4 {
+            if prc == Operator::Pow.precedence() {
+                Self::apply_ops_rtl(&mut values, &mut ops, prc)?;
+            } else {
+                Self::apply_ops(&mut values, &mut ops, prc)?;
+            }
         }
         Ok(values[0])
     }
@@ -184,4 +306,97 @@ fn test() {
         vec![one.clone(), Expression::from(0)], vec![Operator::Div]
     )); // 1/0
     assert_eq!(j.eval(), Err(EvaluationError::ZeroDivision));
-}
\ No newline at end of file
+
+    let k = Expression::from(Parentheses::new(
+        vec![two.clone(), three.clone()], vec![Operator::Pow]
+    )); // 2^3
+    assert_eq!(k.eval(), Expression::from(8).eval());
+
+    let l = Expression::from(Parentheses::new(
+        vec![two.clone(), three.clone(), two.clone()], vec![Operator::Pow, Operator::Pow]
+    )); // 2^3^2 = 2^(3^2) = 512
+    assert_eq!(l.eval(), Expression::from(512).eval());
+
+    let m = Expression::from(Parentheses::new(
+        vec![two.clone(), Expression::from(0)], vec![Operator::Pow]
+    )); // 2^0
+    assert_eq!(m.eval(), one.eval());
+
+    // A huge exponent on a base whose running product never overflows
+    // (so a linear count-down loop would never short-circuit) must still
+    // resolve in O(log exponent) steps rather than hanging.
+    let huge_pow = Expression::from(Parentheses::new(
+        vec![one.clone(), Expression::from(u64::MAX)], vec![Operator::Pow]
+    )); // 1^u64::MAX
+    assert_eq!(huge_pow.eval(), one.eval());
+
+    let neg_two = Expression::Neg(Box::new(two.clone()));
+    let neg_pow = Expression::from(Parentheses::new(
+        vec![two.clone(), neg_two.clone()], vec![Operator::Pow]
+    )); // 2^-2 = 1/4
+    assert_eq!(neg_pow.eval(), Ok(Value::Number(Fraction::new(1u64, 4u64))));
+
+    let zero_neg_pow = Expression::from(Parentheses::new(
+        vec![Expression::from(0), neg_two.clone()], vec![Operator::Pow]
+    )); // 0^-2
+    assert_eq!(zero_neg_pow.eval(), Err(EvaluationError::ZeroDivision));
+
+    let half = Expression::from(Parentheses::new(
+        vec![one.clone(), two.clone()], vec![Operator::Div]
+    )); // 1/2, a genuinely non-integer exponent
+    let fractional_pow = Expression::from(Parentheses::new(
+        vec![two.clone(), half], vec![Operator::Pow]
+    )); // 2^(1/2)
+    assert_eq!(fractional_pow.eval(), Err(EvaluationError::NonIntegerExponent));
+
+    let n = Expression::from(Parentheses::new(
+        vec![one.clone(), one.clone()], vec![Operator::Eq]
+    )); // 1==1
+    assert_eq!(n.eval(), Ok(Value::Bool(true)));
+
+    let o = Expression::from(Parentheses::new(
+        vec![one.clone(), two.clone()], vec![Operator::Lt]
+    )); // 1<2
+    assert_eq!(o.eval(), Ok(Value::Bool(true)));
+
+    let p = Expression::from(Parentheses::new(
+        vec![three.clone(), one.clone(), two.clone()], vec![Operator::Gt, Operator::Eq]
+    )); // 3>1 == 2, compares (3>1) with 2 -> type mismatch
+    assert_eq!(p.eval(), Err(EvaluationError::TypeMismatch));
+
+    let q = Expression::from(Parentheses::new(
+        vec![one.clone(), two.clone(), Expression::from(Parentheses::new(
+            vec![one.clone(), one.clone()], vec![Operator::Eq]
+        ))], vec![Operator::Add, Operator::Eq]
+    )); // (1+2) == (1==1), comparing the number 3 with a bool is a type mismatch
+    assert_eq!(q.eval(), Err(EvaluationError::TypeMismatch));
+
+    let one_eq_one = Expression::from(Parentheses::new(
+        vec![one.clone(), one.clone()], vec![Operator::Eq]
+    )); // 1==1
+
+    let r = Expression::from(Parentheses::new(
+        vec![one_eq_one.clone(), one.clone()], vec![Operator::Add]
+    )); // (1==1) + 1, an arithmetic operator rejecting a Bool directly
+    assert_eq!(r.eval(), Err(EvaluationError::TypeMismatch));
+
+    let s = Expression::from(Parentheses::new(
+        vec![one.clone(), one_eq_one.clone()], vec![Operator::Mul]
+    )); // 1 * (1==1)
+    assert_eq!(s.eval(), Err(EvaluationError::TypeMismatch));
+
+    let t = Expression::from(Parentheses::new(
+        vec![two.clone(), two.clone()], vec![Operator::Ge]
+    )); // 2>=2
+    assert_eq!(t.eval(), Ok(Value::Bool(true)));
+
+    let u = Expression::from(Parentheses::new(
+        vec![one.clone(), two.clone()], vec![Operator::Le]
+    )); // 1<=2
+    assert_eq!(u.eval(), Ok(Value::Bool(true)));
+
+    let v = Expression::from(Parentheses::new(
+        vec![one.clone(), two.clone()], vec![Operator::Ne]
+    )); // 1!=2
+    assert_eq!(v.eval(), Ok(Value::Bool(true)));
+}